@@ -3,16 +3,21 @@
 //!
 //! ## Wildcards
 //! * `?` match any byte
+//! * `?0` / `a?` match a byte where only one nibble is fixed (here the low and
+//!   high nibble respectively)
 //!
 //! ## Example Patterns
 //! * `fe 00 68 98` - matches only `fe 00 68 98`
 //! * `8d 11 ? ? 8f` - could match `8d 11 9e ef 8f` or `8d 11 0 0 8f` for example
+//! * `4? 8b` - matches any byte whose high nibble is `4` followed by `8b`
 //!
 //! ## Example Usage
 //! The [`scan`] function is used to scan for a pattern within the output of a [`Read`]. Using a
 //! [`Cursor`](std::io::Cursor) to scan within a byte array in memory could look as follows:
 //!
 //! ```rust
+//! # #[cfg(feature = "std")]
+//! # {
 //! use patternscan::scan;
 //! use std::io::Cursor;
 //!
@@ -20,6 +25,7 @@
 //! let pattern = "20 30 40";
 //! let locs = scan(Cursor::new(bytes), &pattern).unwrap(); // Will equal vec![1], the index of
 //!                                                         // the pattern
+//! # }
 //! ```
 //!
 //! Any struct implementing [`Read`] can be passed as the reader which should be scanned for
@@ -36,9 +42,61 @@
 //!
 //! For more example uses of this module, see the
 //! [tests](https://github.com/lewisclark/patternscan/blob/master/src/lib.rs#L128)
-use std::fmt::{self, Display};
-use std::io::Read;
-use std::str::FromStr;
+//!
+//! ## `no_std`
+//! The default `std` feature provides a blanket [`Read`] impl for every
+//! [`std::io::Read`], so the examples above work unchanged. Disabling it builds
+//! the crate as `no_std` (an allocator is still required): implement the crate's
+//! own [`Read`] trait over your byte source and the same chunked scanning runs
+//! on embedded targets such as flash or MMIO-backed streams.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, vec::Vec};
+
+use core::fmt::{self, Display};
+use core::str::FromStr;
+
+/// Byte-stream source scanned for patterns.
+///
+/// This is a minimal, `core_io`-style stand-in for [`std::io::Read`] so the
+/// crate can scan over embedded byte sources (flash, MMIO-backed streams)
+/// without `std`. With the default `std` feature enabled there is a blanket
+/// implementation for every [`std::io::Read`], so the public `impl Read` API is
+/// unchanged; without it, implement this trait directly over your own source.
+pub trait Read {
+    /// Pull some bytes into `buf`, returning the number of bytes read. A return
+    /// value of `0` signals the end of the stream.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error>;
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> Read for R {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        std::io::Read::read(self, buf).map_err(|_| Error::ReadFailed)
+    }
+}
+
+/// Read every byte produced by `reader` into `buf`.
+///
+/// The `no_std` replacement for [`std::io::Read::read_to_end`], used by the
+/// backward matchers which need the whole byte string buffered up-front.
+fn read_to_end(reader: &mut impl Read, buf: &mut Vec<u8>) -> Result<(), Error> {
+    let mut chunk = [0; CHUNK_SIZE];
+
+    loop {
+        let n = reader.read(&mut chunk)?;
+        if n == 0 {
+            break;
+        }
+
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    Ok(())
+}
 
 /// Size of chunks to be read from `reader` when looking for patterns.
 ///
@@ -47,6 +105,34 @@ use std::str::FromStr;
 /// given by `CHUNK_SIZE`.
 pub const CHUNK_SIZE: usize = 0x800;
 
+/// Background byte-frequency table used to pick the rarest concrete byte of a
+/// [`Pattern`] as a prefilter anchor.
+///
+/// Each entry is a relative frequency score for the corresponding byte value in
+/// typical input, where a higher number means the byte occurs more often. The
+/// table is the same flavour of heuristic that `aho-corasick` ships for its
+/// rare-byte searchers; it does not need to be exact, only good enough to favour
+/// a statistically uncommon anchor byte over a common one.
+#[rustfmt::skip]
+const BYTE_FREQUENCIES: [u8; 256] = [
+    55,  52,  51,  50,  49,  48,  47,  46,  45,  103, 242, 66,  67,  229, 44,  43,
+    42,  41,  40,  39,  38,  37,  36,  35,  34,  33,  56,  32,  31,  30,  29,  28,
+    255, 148, 164, 149, 136, 160, 155, 173, 221, 222, 134, 122, 232, 202, 215, 224,
+    208, 220, 204, 187, 183, 179, 177, 168, 178, 200, 226, 195, 154, 184, 174, 126,
+    120, 191, 157, 194, 170, 189, 162, 161, 150, 193, 142, 137, 171, 176, 185, 167,
+    186, 112, 175, 192, 188, 156, 140, 143, 123, 133, 128, 147, 138, 146, 114, 223,
+    151, 249, 216, 238, 236, 253, 227, 218, 230, 247, 135, 180, 241, 233, 246, 244,
+    231, 139, 245, 243, 250, 237, 214, 166, 203, 181, 152, 144, 252, 145, 106, 254,
+    158, 153, 141, 131, 127, 130, 165, 163, 159, 119, 100, 98,  96,  93,  91,  90,
+    89,  88,  87,  85,  84,  82,  81,  80,  79,  77,  76,  75,  74,  73,  72,  71,
+    70,  69,  68,  65,  64,  63,  62,  61,  60,  59,  58,  57,  54,  53,  27,  26,
+    25,  24,  23,  22,  21,  20,  19,  18,  17,  16,  15,  14,  13,  12,  11,  10,
+    9,   8,   7,   6,   5,   4,   3,   2,   1,   0,   99,  97,  95,  94,  92,  86,
+    83,  78,  125, 124, 121, 118, 117, 116, 115, 113, 111, 110, 109, 108, 107, 105,
+    104, 102, 101, 132, 129, 169, 172, 182, 190, 196, 197, 198, 199, 201, 205, 206,
+    207, 209, 210, 211, 212, 213, 217, 219, 225, 228, 234, 235, 239, 240, 248, 251,
+];
+
 /// Scan for any instances of `pattern` in the bytes read by `reader`.
 ///
 /// Returns a [`Result`] containing a vector of indices of the start of each match within the
@@ -76,6 +162,44 @@ pub fn scan_first_match(reader: impl Read, pattern: &str) -> Result<Option<usize
     matches.next().transpose()
 }
 
+/// Scan for the last instance of `pattern` in the bytes read by `reader`.
+///
+/// This is the backward counterpart of [`scan_first_match`]: it returns the
+/// index of the final match rather than the first, which is handy when patching
+/// the last copy of an instruction. Because the input is a [`Read`], the whole
+/// byte string is buffered in memory and walked from the end via the
+/// [`Searcher`] abstraction.
+///
+/// Returns a [`Result`] containing an [`Option`], which is `Some(index)` if the
+/// pattern was found and `None` otherwise. Returns an [`Error`] if the pattern
+/// is invalid or the reader encounters an error.
+pub fn scan_last_match(mut reader: impl Read, pattern: &str) -> Result<Option<usize>, Error> {
+    let pattern = Pattern::from_str(pattern)?;
+
+    let mut bytes = Vec::new();
+    read_to_end(&mut reader, &mut bytes)?;
+
+    Ok(SliceSearcher::new(&bytes, &pattern).next_match_back())
+}
+
+/// Scan an in-memory byte slice for every instance of `pattern`, returning the
+/// match indices in ascending order.
+///
+/// This is the in-memory core shared by the forward and backward matchers; it
+/// drives a `Searcher` over `bytes` rather than reading from a [`Read`] in
+/// chunks, so it is only suitable when the whole byte string is already
+/// available.
+pub fn scan_all_in_slice(bytes: &[u8], pattern: &Pattern) -> Vec<usize> {
+    let mut searcher = SliceSearcher::new(bytes, pattern);
+    let mut locs = Vec::new();
+
+    while let Some(i) = searcher.next_match() {
+        locs.push(i);
+    }
+
+    locs
+}
+
 /// Determine whether a byte slice matches a pattern.
 pub fn pattern_matches(bytes: &[u8], pattern: &Pattern) -> bool {
     if bytes.len() < pattern.len() {
@@ -85,32 +209,57 @@ pub fn pattern_matches(bytes: &[u8], pattern: &Pattern) -> bool {
     }
 }
 
-/// Represents an error which occurred while scanning for a pattern.
-#[derive(Debug)]
-pub struct Error {
-    /// String detailing the error
-    e: String,
+/// Return the index of the first occurrence of `needle` in `haystack`.
+///
+/// Used as the prefilter step in [`Matches`] to skip over stretches of bytes
+/// which cannot contain a pattern's rare anchor byte: a single byte compare
+/// replaces the full [`pattern_matches`] verification at every offset that
+/// doesn't hold the anchor. This is a plain linear scan, not a SIMD-accelerated
+/// search; swap in a vectorized implementation here if that becomes a
+/// bottleneck.
+fn find_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|&b| b == needle)
 }
 
-impl Error {
-    pub fn new(e: String) -> Self {
-        Self { e }
-    }
+/// Represents an error which occurred while scanning for a pattern.
+///
+/// This is a `no_std`-friendly enum rather than a string, so the crate can
+/// report failures on targets without an allocator-backed [`String`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A pattern token was not a valid 8-bit hexadecimal byte or wildcard.
+    InvalidHexByte,
+    /// A pattern was longer than [`CHUNK_SIZE`] allows it to be.
+    PatternTooLong,
+    /// The underlying reader returned an error.
+    ReadFailed,
 }
 
 impl Display for Error {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        write!(fmt, "Pattern scan error: {}", self.e)
+        let msg = match self {
+            Error::InvalidHexByte => "invalid hex byte in pattern",
+            Error::PatternTooLong => "pattern too long",
+            Error::ReadFailed => "failed to read from reader",
+        };
+
+        write!(fmt, "Pattern scan error: {}", msg)
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
 /// Represents a single byte in a search pattern.
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub enum PatternByte {
     Byte(u8),
     Any,
+    /// A byte which is only partially fixed: a byte `b` matches when
+    /// `b & mask == value`. `Byte(v)` is the `mask == 0xff` case and `Any` is
+    /// the `mask == 0x00` case; this variant covers everything in between, such
+    /// as a token with a single wildcard nibble.
+    Masked { value: u8, mask: u8 },
 }
 
 impl FromStr for PatternByte {
@@ -118,16 +267,38 @@ impl FromStr for PatternByte {
 
     /// Create an instance of [`PatternByte`] from a string.
     ///
-    /// This string should either be a hexadecimal byte, or a "?". Will return an error if the
-    /// string is not a "?", or it cannot be converted into an 8-bit integer when interpreted as
+    /// This string should be a "?" (match any byte), a hexadecimal byte, or a
+    /// two-character token with one wildcard nibble such as "?0" or "a?". Will
+    /// return an error if a non-wildcard nibble cannot be interpreted as
     /// hexadecimal.
     fn from_str(s: &str) -> Result<Self, Error> {
         if s == "?" {
             Ok(Self::Any)
+        } else if s.contains('?') {
+            // A two-character token may wildcard an individual nibble, lowering
+            // to a (value, mask) pair with the wildcard nibble masked out.
+            let chars: Vec<char> = s.chars().collect();
+            if chars.len() != 2 {
+                return Err(Error::InvalidHexByte);
+            }
+
+            let (hi, hi_mask) = nibble(chars[0])?;
+            let (lo, lo_mask) = nibble(chars[1])?;
+            let mask = (hi_mask << 4) | lo_mask;
+
+            // A token with both nibbles wildcarded is just "match any byte".
+            if mask == 0x00 {
+                Ok(Self::Any)
+            } else {
+                Ok(Self::Masked {
+                    value: (hi << 4) | lo,
+                    mask,
+                })
+            }
         } else {
             let n = match u8::from_str_radix(s, 16) {
                 Ok(n) => Ok(n),
-                Err(e) => Err(Error::new(format!("from_str_radix failed: {}", e))),
+                Err(_) => Err(Error::InvalidHexByte),
             }?;
 
             Ok(Self::Byte(n))
@@ -140,24 +311,61 @@ impl PartialEq<u8> for PatternByte {
         match self {
             PatternByte::Any => true,
             PatternByte::Byte(b) => b == other,
+            PatternByte::Masked { value, mask } => (other & mask) == *value,
+        }
+    }
+}
+
+/// Lower a single nibble character to its `(value, mask)` pair, where a "?"
+/// becomes a wildcard nibble `(0x0, 0x0)` and a hex digit becomes `(digit, 0xf)`.
+fn nibble(c: char) -> Result<(u8, u8), Error> {
+    if c == '?' {
+        Ok((0x0, 0x0))
+    } else {
+        match c.to_digit(16) {
+            Some(d) => Ok((d as u8, 0xf)),
+            None => Err(Error::InvalidHexByte),
         }
     }
 }
 
 /// Represents a pattern to search for in a byte string.
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct Pattern {
     bytes: Vec<PatternByte>,
+    /// Rarest non-wildcard byte together with its offset within the pattern,
+    /// used to drive the [`find_byte`] prefilter in [`Matches`]. `None` when the
+    /// pattern is made entirely of `Any` bytes and so has no anchor to skip to.
+    anchor: Option<(u8, usize)>,
 }
 
 impl Pattern {
     fn new(bytes: Vec<PatternByte>) -> Self {
-        Self { bytes }
+        let anchor = Self::find_anchor(&bytes);
+        Self { bytes, anchor }
     }
 
     fn len(&self) -> usize {
         self.bytes.len()
     }
+
+    /// Pick the non-wildcard byte whose value is statistically rarest according
+    /// to [`BYTE_FREQUENCIES`], returning it with its offset within the pattern.
+    ///
+    /// Returns `None` if the pattern contains no concrete bytes, in which case
+    /// [`Matches`] falls back to verifying every offset.
+    fn find_anchor(bytes: &[PatternByte]) -> Option<(u8, usize)> {
+        bytes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pb)| match pb {
+                PatternByte::Byte(b) => Some((*b, i)),
+                // Masked bytes are not a single concrete value, so they cannot
+                // serve as a prefilter anchor.
+                PatternByte::Any | PatternByte::Masked { .. } => None,
+            })
+            .min_by_key(|(b, _)| BYTE_FREQUENCIES[*b as usize])
+    }
 }
 
 impl FromStr for Pattern {
@@ -191,6 +399,8 @@ impl PartialEq<[u8]> for Pattern {
 ///
 /// ## Example Usage
 /// ```rust
+/// # #[cfg(feature = "std")]
+/// # {
 /// use patternscan;
 /// use std::io::Cursor;
 ///
@@ -199,6 +409,7 @@ impl PartialEq<[u8]> for Pattern {
 /// let pattern = patternscan::Matches::from_pattern_str(reader, "20 30").unwrap();
 /// let match_indices: Result<Vec<usize>, _> = pattern.collect();
 /// let match_indices = match_indices.unwrap();
+/// # }
 /// ```
 pub struct Matches<R: Read> {
     /// Reader from which the byte string to search will be read.
@@ -211,6 +422,10 @@ pub struct Matches<R: Read> {
     last_bytes_read: usize,
     abs_position: usize,
     rel_position: usize,
+    // Whether a chunk boundary has been crossed yet, meaning the first `len`
+    // bytes of the buffer are carried over from the previous chunk. Used to work
+    // out how many bytes of the buffer are actually valid.
+    copied: bool,
 }
 
 impl<R: Read> Matches<R> {
@@ -222,10 +437,7 @@ impl<R: Read> Matches<R> {
         // Constraint imposed due to the method used to detect matches over chunk boundaries. We
         // might want to increase the chunk size to account for this?
         if 2 * pattern.len() > CHUNK_SIZE {
-            return Err(Error::new(format!(
-                "Pattern too long: It can be at most {} bytes",
-                CHUNK_SIZE / 2
-            )));
+            return Err(Error::PatternTooLong);
         }
 
         // Perform initial read into the bytes buffer on creation
@@ -233,9 +445,7 @@ impl<R: Read> Matches<R> {
         // I'm not sure, but this ensures that the state of the struct when an instance is created
         // is reasonable.
         let mut bytes_buf = [0; CHUNK_SIZE];
-        let bytes_read = reader
-            .read(&mut bytes_buf)
-            .map_err(|e| Error::new(format!("Failed to read from reader: {}", e)))?;
+        let bytes_read = reader.read(&mut bytes_buf)?;
 
         Ok(Self {
             reader,
@@ -244,6 +454,7 @@ impl<R: Read> Matches<R> {
             last_bytes_read: bytes_read,
             abs_position: 0,
             rel_position: 0,
+            copied: false,
         })
     }
 
@@ -258,52 +469,458 @@ impl<R: Read> Iterator for Matches<R> {
     type Item = Result<usize, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let len = self.pattern.len();
+
         loop {
-            if self.rel_position == CHUNK_SIZE - self.pattern.len() {
-                // This block is what allows us to detect matches over chunk boundaries.
-                // When we're close enough to a boundary that a pattern match could overrun, we
-                // copy the final bytes in the buffer to the start of the buffer, then read into
-                // the rest of the buffer.
-                let len = self.pattern.len();
-
-                let boundary_bytes = &self.bytes_buf[CHUNK_SIZE - len..].to_owned();
-                self.bytes_buf[..len].copy_from_slice(&boundary_bytes);
-
-                self.last_bytes_read = match self.reader.read(&mut self.bytes_buf[len..]) {
-                    Ok(b) => b,
-                    Err(e) => return Some(Err(Error::new(format!("Failed to read bytes: {}", e)))),
+            // Number of valid bytes currently held in the buffer from index 0:
+            // once a boundary has been crossed the first `len` bytes are the tail
+            // carried over from the previous chunk.
+            let buf_len = if self.copied {
+                len + self.last_bytes_read
+            } else {
+                self.last_bytes_read
+            };
+            // The read filled the buffer to capacity, so there may be more data
+            // to come and a match could run across the upcoming chunk boundary.
+            let buffer_full = self.last_bytes_read
+                == if self.copied {
+                    CHUNK_SIZE - len
+                } else {
+                    CHUNK_SIZE
                 };
-
-                self.rel_position = 0;
+            // One past the highest candidate start offset: a start `i` is only
+            // valid when the whole pattern fits within the valid bytes, so we
+            // stop at `buf_len - len`. This matches the bound the backward
+            // [`SliceSearcher`] uses, so forward and backward scanning agree on
+            // trailing bytes rather than reading into buffer padding.
+            let scan_end = if buf_len >= len { buf_len - len + 1 } else { 0 };
+            // When the buffer is full we only verify up to the boundary window
+            // and let the cross-chunk copy below handle the overrun.
+            let end = scan_end.min(CHUNK_SIZE - len);
+
+            match self.pattern.anchor {
+                Some((byte, offset)) => {
+                    // Absolute index of `bytes_buf[0]` for this chunk cycle.
+                    let base = self.abs_position - self.rel_position;
+                    // A candidate alignment `i` places the anchor byte at
+                    // `i + offset`, so hunt for the anchor instead of walking
+                    // every offset. A hit at buffer index `h` implies the single
+                    // candidate `i = h - offset`.
+                    let mut p = self.rel_position + offset;
+                    let search_end = end + offset;
+                    while p <= search_end {
+                        match find_byte(byte, &self.bytes_buf[p..search_end]) {
+                            Some(hit) => {
+                                let h = p + hit;
+                                let i = h - offset;
+                                self.rel_position = i + 1;
+                                self.abs_position = base + i + 1;
+                                if pattern_matches(&self.bytes_buf[i..], &self.pattern) {
+                                    return Some(Ok(base + i));
+                                }
+                                p = h + 1;
+                            }
+                            None => break,
+                        }
+                    }
+
+                    // No further anchor in this chunk; advance to the boundary so
+                    // the next iteration either copies across it or terminates.
+                    self.rel_position = end;
+                    self.abs_position = base + end;
+                }
+                None => {
+                    while self.rel_position < end {
+                        let i = self.rel_position;
+                        self.abs_position += 1;
+                        self.rel_position += 1;
+                        if pattern_matches(&self.bytes_buf[i..], &self.pattern) {
+                            return Some(Ok(self.abs_position - 1));
+                        }
+                    }
+                }
             }
 
-            if self.rel_position == self.last_bytes_read + self.pattern.len() {
+            if !buffer_full {
+                // The whole byte string has been scanned.
                 break;
             }
 
-            for i in self.rel_position..self.last_bytes_read + self.pattern.len() {
-                if i == CHUNK_SIZE - self.pattern.len() {
-                    break;
+            // Detect matches over chunk boundaries: copy the final `len` bytes of
+            // the buffer to the start, then read into the rest of the buffer.
+            let boundary_bytes = &self.bytes_buf[CHUNK_SIZE - len..].to_owned();
+            self.bytes_buf[..len].copy_from_slice(boundary_bytes);
+
+            self.last_bytes_read = match self.reader.read(&mut self.bytes_buf[len..]) {
+                Ok(b) => b,
+                Err(e) => return Some(Err(e)),
+            };
+
+            self.copied = true;
+            self.rel_position = 0;
+        }
+
+        None
+    }
+}
+
+/// Identifies one of the patterns within a [`PatternSet`] by its position in
+/// the `Vec<Pattern>` it was built from (the first pattern is `PatternId(0)`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PatternId(pub usize);
+
+/// Determines which pattern wins when several patterns of a [`PatternSet`] match
+/// at the same offset.
+///
+/// This mirrors the match semantics offered by multi-pattern searchers such as
+/// those in `aho-corasick`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchKind {
+    /// Return the pattern which was supplied earliest in the set.
+    #[default]
+    LeftmostFirst,
+    /// Return the longest matching pattern, breaking ties by supply order.
+    LeftmostLongest,
+}
+
+/// A collection of [`Pattern`]s to scan for in a single pass over a reader.
+///
+/// Scanning a [`Read`] for many signatures at once with [`scan_multiple`] (or
+/// the streaming [`MultiMatches`] iterator) only reads the underlying byte
+/// string once, rather than once per pattern. The [`MatchKind`] decides which
+/// pattern is reported when more than one matches at the same offset.
+#[derive(Clone)]
+pub struct PatternSet {
+    patterns: Vec<Pattern>,
+    kind: MatchKind,
+}
+
+impl PatternSet {
+    /// Create a [`PatternSet`] from a list of patterns, using the default
+    /// [`MatchKind::LeftmostFirst`] semantics.
+    pub fn new(patterns: Vec<Pattern>) -> Self {
+        Self {
+            patterns,
+            kind: MatchKind::default(),
+        }
+    }
+
+    /// Create a [`PatternSet`] from a list of patterns with an explicit
+    /// [`MatchKind`].
+    pub fn with_match_kind(patterns: Vec<Pattern>, kind: MatchKind) -> Self {
+        Self { patterns, kind }
+    }
+}
+
+/// Scan for any of the patterns in `set` within the bytes read by `reader`.
+///
+/// Returns a [`Result`] containing a vector of `(PatternId, index)` pairs, one
+/// per match, where `index` is the start of the match within the bytes and
+/// `PatternId` identifies which pattern of the set matched there. When several
+/// patterns match at the same offset, the one reported is chosen according to
+/// the set's [`MatchKind`]. Returns an [`Error`] if the reader encounters an
+/// error, or if any pattern is longer than [`CHUNK_SIZE`] allows.
+pub fn scan_multiple(
+    reader: impl Read,
+    set: &PatternSet,
+) -> Result<Vec<(PatternId, usize)>, Error> {
+    let matches = MultiMatches::new(reader, set.clone())?;
+    matches.collect()
+}
+
+/// Iterator over locations of matches for any pattern in a [`PatternSet`].
+///
+/// This is the multi-pattern counterpart of [`Matches`]. It reads the byte
+/// string produced by `reader` in [`CHUNK_SIZE`] chunks (sharing a single
+/// buffer across every pattern) and, at each offset, reports the winning
+/// pattern for the set's [`MatchKind`]. The cross-chunk-boundary handling is the
+/// same as [`Matches`], sized to the longest pattern in the set.
+pub struct MultiMatches<R: Read> {
+    reader: R,
+    set: PatternSet,
+    max_len: usize,
+
+    bytes_buf: [u8; CHUNK_SIZE],
+    last_bytes_read: usize,
+    abs_position: usize,
+    rel_position: usize,
+    // Whether a chunk boundary has been crossed yet, meaning the first `max_len`
+    // bytes of the buffer are carried over from the previous chunk. Used to work
+    // out how many bytes of the buffer are actually valid.
+    copied: bool,
+}
+
+impl<R: Read> MultiMatches<R> {
+    /// Create a new instance of [`MultiMatches`] from a [`PatternSet`].
+    pub fn new(mut reader: R, set: PatternSet) -> Result<Self, Error> {
+        let max_len = set.patterns.iter().map(|p| p.len()).max().unwrap_or(0);
+
+        // Same chunk-boundary constraint as `Matches`, but bounded by the
+        // longest pattern in the set.
+        if 2 * max_len > CHUNK_SIZE {
+            return Err(Error::PatternTooLong);
+        }
+
+        let mut bytes_buf = [0; CHUNK_SIZE];
+        let bytes_read = reader.read(&mut bytes_buf)?;
+
+        Ok(Self {
+            reader,
+            set,
+            max_len,
+            bytes_buf,
+            last_bytes_read: bytes_read,
+            abs_position: 0,
+            rel_position: 0,
+            copied: false,
+        })
+    }
+
+    /// Return the winning pattern matching at buffer index `i`, if any, applying
+    /// the set's [`MatchKind`] when more than one pattern matches there.
+    ///
+    /// Only the bytes up to `valid` are considered, so a pattern is never
+    /// verified against the buffer's zero padding (or stale bytes after a short
+    /// read) past the end of the stream.
+    fn select(&self, i: usize, valid: usize) -> Option<PatternId> {
+        let bytes = &self.bytes_buf[i..valid];
+        let mut best: Option<(PatternId, usize)> = None;
+
+        for (idx, pattern) in self.set.patterns.iter().enumerate() {
+            // Same rare-byte prefilter as `Matches`: a pattern with an anchor
+            // byte can only match here if that byte is actually present at its
+            // offset, so rule it out with a single comparison before paying for
+            // the full `pattern_matches` verification.
+            if let Some((byte, offset)) = pattern.anchor {
+                if bytes.get(offset) != Some(&byte) {
+                    continue;
+                }
+            }
+
+            if !pattern_matches(bytes, pattern) {
+                continue;
+            }
+
+            let id = PatternId(idx);
+            match self.set.kind {
+                // Patterns are visited in supply order, so the first match is
+                // the leftmost-first winner.
+                MatchKind::LeftmostFirst => return Some(id),
+                // Replace only on a strictly longer pattern so ties keep the
+                // earliest-supplied one.
+                MatchKind::LeftmostLongest => {
+                    if best.is_none_or(|(_, len)| pattern.len() > len) {
+                        best = Some((id, pattern.len()));
+                    }
                 }
+            }
+        }
+
+        best.map(|(id, _)| id)
+    }
+}
+
+impl<R: Read> Iterator for MultiMatches<R> {
+    type Item = Result<(PatternId, usize), Error>;
 
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.max_len;
+
+        loop {
+            // Number of valid bytes currently held in the buffer from index 0:
+            // once a boundary has been crossed the first `max_len` bytes are the
+            // tail carried over from the previous chunk.
+            let buf_len = if self.copied {
+                len + self.last_bytes_read
+            } else {
+                self.last_bytes_read
+            };
+            // The read filled the buffer to capacity, so there may be more data
+            // to come and a match could run across the upcoming chunk boundary.
+            let buffer_full = self.last_bytes_read
+                == if self.copied {
+                    CHUNK_SIZE - len
+                } else {
+                    CHUNK_SIZE
+                };
+            // When the buffer is full we stop at the boundary window and let the
+            // cross-chunk copy below handle the overrun; otherwise every valid
+            // byte is a candidate start, with `select` clamping each pattern to
+            // the real data so none is verified against the padding. `buf_len + 1`
+            // (rather than `buf_len`) allows a start index one past the end, which
+            // only a zero-length pattern can match, keeping this in step with the
+            // single-pattern `Matches::next` and the backward searchers.
+            let end = if buffer_full { CHUNK_SIZE - len } else { buf_len + 1 };
+
+            while self.rel_position < end {
+                let i = self.rel_position;
                 self.abs_position += 1;
                 self.rel_position += 1;
-                if pattern_matches(&self.bytes_buf[i..], &self.pattern) {
-                    return Some(Ok(self.abs_position - 1));
+                if let Some(id) = self.select(i, buf_len) {
+                    return Some(Ok((id, self.abs_position - 1)));
                 }
             }
 
-            if self.last_bytes_read == 0 {
+            if !buffer_full {
+                // The whole byte string has been scanned.
                 break;
             }
+
+            let boundary_bytes = &self.bytes_buf[CHUNK_SIZE - len..].to_owned();
+            self.bytes_buf[..len].copy_from_slice(boundary_bytes);
+
+            self.last_bytes_read = match self.reader.read(&mut self.bytes_buf[len..]) {
+                Ok(b) => b,
+                Err(e) => return Some(Err(e)),
+            };
+
+            self.copied = true;
+            self.rel_position = 0;
         }
 
         None
     }
 }
 
+/// One past the highest candidate start offset for a pattern of length
+/// `pattern_len` within `bytes_len` bytes.
+///
+/// For a zero-length pattern this is `bytes_len + 1`, matching the forward
+/// scan's `scan_end`, which likewise allows a start index one past the end so
+/// both directions agree on the same count of (empty) matches.
+fn reverse_start(bytes_len: usize, pattern_len: usize) -> usize {
+    if pattern_len > bytes_len {
+        0
+    } else {
+        bytes_len - pattern_len + 1
+    }
+}
+
+/// Iterates the candidate match offsets of a [`Pattern`] within a byte string,
+/// in either direction.
+///
+/// This mirrors the `Searcher`/`ReverseSearcher` split of
+/// [`std::str::pattern`], factoring the forward and backward offset iteration
+/// out so both share the same [`pattern_matches`] verification. Returned values
+/// are the start indices of matches.
+pub trait Searcher {
+    /// Return the next match offset scanning forward, or `None` when the
+    /// remaining range has been exhausted.
+    fn next_match(&mut self) -> Option<usize>;
+
+    /// Return the next match offset scanning backward, or `None` when the
+    /// remaining range has been exhausted.
+    fn next_match_back(&mut self) -> Option<usize>;
+}
+
+/// A [`Searcher`] over an in-memory byte slice.
+///
+/// The candidate start offsets `0..=bytes.len() - pattern.len()` are tracked as
+/// the half-open range `[front, back)`, so forward and backward iteration share
+/// state and never report the same offset twice.
+struct SliceSearcher<'a> {
+    bytes: &'a [u8],
+    pattern: &'a Pattern,
+    front: usize,
+    back: usize,
+}
+
+impl<'a> SliceSearcher<'a> {
+    fn new(bytes: &'a [u8], pattern: &'a Pattern) -> Self {
+        Self {
+            bytes,
+            pattern,
+            front: 0,
+            back: reverse_start(bytes.len(), pattern.len()),
+        }
+    }
+}
+
+impl<'a> Searcher for SliceSearcher<'a> {
+    fn next_match(&mut self) -> Option<usize> {
+        while self.front < self.back {
+            let i = self.front;
+            self.front += 1;
+            if pattern_matches(&self.bytes[i..], self.pattern) {
+                return Some(i);
+            }
+        }
+
+        None
+    }
+
+    fn next_match_back(&mut self) -> Option<usize> {
+        while self.front < self.back {
+            self.back -= 1;
+            let i = self.back;
+            if pattern_matches(&self.bytes[i..], self.pattern) {
+                return Some(i);
+            }
+        }
+
+        None
+    }
+}
+
+/// Iterator over pattern match indices of an in-memory byte string, yielded from
+/// the highest index to the lowest.
+///
+/// Unlike [`Matches`], which streams over any [`Read`] in [`CHUNK_SIZE`] chunks,
+/// this adapter buffers the entire input up-front (so it suits
+/// [`Cursor`](std::io::Cursor) and slice inputs) in order to walk matches
+/// backward. It is the reverse counterpart of [`scan`], convenient when you
+/// only care about the final occurrences of a signature.
+pub struct ReverseMatches {
+    bytes: Vec<u8>,
+    pattern: Pattern,
+    back: usize,
+}
+
+impl ReverseMatches {
+    /// Create a new instance of [`ReverseMatches`] from an instance of
+    /// [`Pattern`], buffering everything produced by `reader`.
+    pub fn from_pattern(mut reader: impl Read, pattern: Pattern) -> Result<Self, Error> {
+        let mut bytes = Vec::new();
+        read_to_end(&mut reader, &mut bytes)?;
+
+        let back = reverse_start(bytes.len(), pattern.len());
+
+        Ok(Self {
+            bytes,
+            pattern,
+            back,
+        })
+    }
+
+    /// Create a new instance of [`ReverseMatches`] from a string pattern.
+    pub fn from_pattern_str(reader: impl Read, pattern: &str) -> Result<Self, Error> {
+        Self::from_pattern(reader, Pattern::from_str(pattern)?)
+    }
+}
+
+impl Iterator for ReverseMatches {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let mut searcher = SliceSearcher {
+            bytes: &self.bytes,
+            pattern: &self.pattern,
+            front: 0,
+            back: self.back,
+        };
+        let m = searcher.next_match_back();
+        self.back = searcher.back;
+
+        m
+    }
+}
+
 // Tests
-#[cfg(test)]
+//
+// These exercise the public API through `std::io::Cursor`, so they only make
+// sense with the `std` feature enabled; the `no_std` configuration is covered
+// separately below, directly against the crate's own `Read` trait.
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use std::io::Cursor;
 
@@ -312,7 +929,7 @@ mod tests {
         let bytes = [0x10, 0x20, 0x30, 0x40, 0x50];
         let pattern = "10 20 30";
 
-        assert_eq!(crate::scan(Cursor::new(bytes), &pattern).unwrap(), vec![0]);
+        assert_eq!(crate::scan(Cursor::new(bytes), pattern).unwrap(), vec![0]);
     }
 
     #[test]
@@ -320,7 +937,7 @@ mod tests {
         let bytes = [0x10, 0x20, 0x30, 0x40, 0x50];
         let pattern = "20 30 40";
 
-        assert_eq!(crate::scan(Cursor::new(bytes), &pattern).unwrap(), vec![1]);
+        assert_eq!(crate::scan(Cursor::new(bytes), pattern).unwrap(), vec![1]);
     }
 
     #[test]
@@ -328,7 +945,7 @@ mod tests {
         let bytes = [0x10, 0x20, 0x30, 0x40, 0x50];
         let pattern = "40 50 60";
 
-        assert_eq!(crate::scan(Cursor::new(bytes), &pattern).unwrap(), vec![]);
+        assert_eq!(crate::scan(Cursor::new(bytes), pattern).unwrap(), vec![]);
     }
 
     #[test]
@@ -336,7 +953,7 @@ mod tests {
         let bytes = [0xff, 0xfe, 0x7c, 0x88, 0xfd, 0x90, 0x00];
         let pattern = "fe 7c 88 fd 90 0";
 
-        assert_eq!(crate::scan(Cursor::new(bytes), &pattern).unwrap(), vec![1]);
+        assert_eq!(crate::scan(Cursor::new(bytes), pattern).unwrap(), vec![1]);
     }
 
     #[test]
@@ -344,7 +961,7 @@ mod tests {
         let bytes = [0xff, 0xfe, 0x7c, 0x88, 0xfd, 0x90, 0x00];
         let pattern = "fe ? ? ? 90";
 
-        assert_eq!(crate::scan(Cursor::new(bytes), &pattern).unwrap(), vec![1]);
+        assert_eq!(crate::scan(Cursor::new(bytes), pattern).unwrap(), vec![1]);
     }
 
     #[test]
@@ -352,7 +969,7 @@ mod tests {
         let bytes = [0xff, 0xfe, 0x7c, 0x88, 0xfd, 0x90, 0x00];
         let pattern = "? ? ? ? fd";
 
-        assert_eq!(crate::scan(Cursor::new(bytes), &pattern).unwrap(), vec![0]);
+        assert_eq!(crate::scan(Cursor::new(bytes), pattern).unwrap(), vec![0]);
     }
 
     #[test]
@@ -360,7 +977,7 @@ mod tests {
         let bytes = [0xff, 0xfe, 0x7c, 0x88, 0xfd, 0x90, 0x00];
         let pattern = "78 90 cc dd fe";
 
-        assert_eq!(crate::scan(Cursor::new(bytes), &pattern).unwrap(), vec![]);
+        assert_eq!(crate::scan(Cursor::new(bytes), pattern).unwrap(), vec![]);
     }
 
     #[test]
@@ -368,7 +985,7 @@ mod tests {
         let bytes = [0xff, 0xfe, 0x7c, 0x88, 0xfd, 0x90, 0x00];
         let pattern = "fe 7c 88 fd 90 1";
 
-        assert_eq!(crate::scan(Cursor::new(bytes), &pattern).unwrap(), vec![]);
+        assert_eq!(crate::scan(Cursor::new(bytes), pattern).unwrap(), vec![]);
     }
 
     #[test]
@@ -376,7 +993,7 @@ mod tests {
         let bytes = [0xff, 0xfe, 0x7c, 0x88, 0xfd, 0x90, 0x00];
         let pattern = "fe 7c 88 fd 90 0 1";
 
-        assert_eq!(crate::scan(Cursor::new(bytes), &pattern).unwrap(), vec![]);
+        assert_eq!(crate::scan(Cursor::new(bytes), pattern).unwrap(), vec![]);
     }
 
     #[test]
@@ -385,7 +1002,7 @@ mod tests {
         let pattern = "10 20 30";
 
         assert_eq!(
-            crate::scan(Cursor::new(bytes), &pattern).unwrap(),
+            crate::scan(Cursor::new(bytes), pattern).unwrap(),
             vec![0, 3]
         );
     }
@@ -396,7 +1013,7 @@ mod tests {
         let pattern = "10 ? 30";
 
         assert_eq!(
-            crate::scan(Cursor::new(bytes), &pattern).unwrap(),
+            crate::scan(Cursor::new(bytes), pattern).unwrap(),
             vec![0, 3]
         );
     }
@@ -406,7 +1023,7 @@ mod tests {
         let bytes = [0x10, 0x20, 0x30];
         let pattern = "10 fff 20";
 
-        assert!(crate::scan(Cursor::new(bytes), &pattern).is_err());
+        assert!(crate::scan(Cursor::new(bytes), pattern).is_err());
     }
 
     #[test]
@@ -415,7 +1032,7 @@ mod tests {
         let pattern = "10 20 30";
 
         assert_eq!(
-            crate::scan_first_match(Cursor::new(bytes), &pattern)
+            crate::scan_first_match(Cursor::new(bytes), pattern)
                 .unwrap()
                 .unwrap(),
             0
@@ -428,7 +1045,7 @@ mod tests {
         let pattern = "20 30 40";
 
         assert_eq!(
-            crate::scan_first_match(Cursor::new(bytes), &pattern)
+            crate::scan_first_match(Cursor::new(bytes), pattern)
                 .unwrap()
                 .unwrap(),
             1
@@ -440,11 +1057,339 @@ mod tests {
         let bytes = [0x10, 0x20, 0x30, 0x40, 0x50];
         let pattern = "10 11 12";
 
-        assert!(crate::scan_first_match(Cursor::new(bytes), &pattern)
+        assert!(crate::scan_first_match(Cursor::new(bytes), pattern)
             .unwrap()
             .is_none());
     }
 
+    #[test]
+    fn scan_prefilter_anchor_not_first() {
+        // The rarest byte here (0xc9) sits in the middle of the pattern, so the
+        // prefilter anchors on an offset greater than zero.
+        let bytes = [0x00, 0x10, 0xc9, 0x20, 0x00, 0x10, 0xc9, 0x20];
+        let pattern = "10 c9 20";
+
+        assert_eq!(
+            crate::scan(Cursor::new(bytes), pattern).unwrap(),
+            vec![1, 5]
+        );
+    }
+
+    #[test]
+    fn scan_all_wildcards_falls_back() {
+        let bytes = [0x10, 0x20, 0x30];
+        let pattern = "? ?";
+
+        assert_eq!(
+            crate::scan_first_match(Cursor::new(bytes), pattern)
+                .unwrap()
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn scan_multiple_reports_pattern_ids() {
+        use crate::{Pattern, PatternId, PatternSet};
+        use std::str::FromStr;
+
+        let bytes = [0x10, 0x20, 0x30, 0x40, 0x10, 0x20, 0x50];
+        let set = PatternSet::new(vec![
+            Pattern::from_str("10 20 30").unwrap(),
+            Pattern::from_str("10 20 50").unwrap(),
+        ]);
+
+        assert_eq!(
+            crate::scan_multiple(Cursor::new(bytes), &set).unwrap(),
+            vec![(PatternId(0), 0), (PatternId(1), 4)]
+        );
+    }
+
+    #[test]
+    fn scan_multiple_leftmost_first() {
+        use crate::{MatchKind, Pattern, PatternId, PatternSet};
+        use std::str::FromStr;
+
+        let bytes = [0x10, 0x20, 0x30, 0x40];
+        // Both match at offset 0; leftmost-first picks the one supplied first.
+        let set = PatternSet::with_match_kind(
+            vec![
+                Pattern::from_str("10 20").unwrap(),
+                Pattern::from_str("10 20 30 40").unwrap(),
+            ],
+            MatchKind::LeftmostFirst,
+        );
+
+        assert_eq!(
+            crate::scan_multiple(Cursor::new(bytes), &set).unwrap(),
+            vec![(PatternId(0), 0)]
+        );
+    }
+
+    #[test]
+    fn scan_multiple_leftmost_longest() {
+        use crate::{MatchKind, Pattern, PatternId, PatternSet};
+        use std::str::FromStr;
+
+        let bytes = [0x10, 0x20, 0x30, 0x40];
+        // Both match at offset 0; leftmost-longest picks the longer pattern.
+        let set = PatternSet::with_match_kind(
+            vec![
+                Pattern::from_str("10 20").unwrap(),
+                Pattern::from_str("10 20 30 40").unwrap(),
+            ],
+            MatchKind::LeftmostLongest,
+        );
+
+        assert_eq!(
+            crate::scan_multiple(Cursor::new(bytes), &set).unwrap(),
+            vec![(PatternId(1), 0)]
+        );
+    }
+
+    #[test]
+    fn scan_multiple_does_not_match_past_end() {
+        use crate::{Pattern, PatternSet};
+        use std::str::FromStr;
+
+        // A pattern whose tail would fall on the buffer's zero padding must not
+        // match past the real data, so `scan_multiple` agrees with `scan`.
+        let bytes = [0x10, 0x20, 0x30];
+        for pattern in ["20 00", "00", "30 00 00"] {
+            let set = PatternSet::new(vec![Pattern::from_str(pattern).unwrap()]);
+
+            assert_eq!(
+                crate::scan_multiple(Cursor::new(bytes), &set).unwrap(),
+                vec![]
+            );
+            assert_eq!(crate::scan(Cursor::new(bytes), pattern).unwrap(), vec![]);
+        }
+    }
+
+    #[test]
+    fn scan_multiple_agrees_with_scan_on_empty_pattern() {
+        use crate::{Pattern, PatternId, PatternSet};
+        use std::str::FromStr;
+
+        // A zero-length pattern matches at every offset, including one past the
+        // end of the data; `scan_multiple` must agree with `scan` here too.
+        let bytes = [0x01, 0x02, 0x03];
+        let set = PatternSet::new(vec![Pattern::from_str("").unwrap()]);
+
+        assert_eq!(
+            crate::scan_multiple(Cursor::new(bytes), &set).unwrap(),
+            vec![
+                (PatternId(0), 0),
+                (PatternId(0), 1),
+                (PatternId(0), 2),
+                (PatternId(0), 3)
+            ]
+        );
+        assert_eq!(crate::scan(Cursor::new(bytes), "").unwrap(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn scan_multiple_matches_shorter_pattern_at_tail() {
+        use crate::{Pattern, PatternId, PatternSet};
+        use std::str::FromStr;
+
+        // A short pattern matching at the very end must still be reported even
+        // when a longer pattern in the set sets `max_len`.
+        let bytes = [0x10, 0x20, 0x30];
+        let set = PatternSet::new(vec![
+            Pattern::from_str("10 20 30").unwrap(),
+            Pattern::from_str("30").unwrap(),
+        ]);
+
+        assert_eq!(
+            crate::scan_multiple(Cursor::new(bytes), &set).unwrap(),
+            vec![(PatternId(0), 0), (PatternId(1), 2)]
+        );
+    }
+
+    #[test]
+    fn scan_multiple_uses_anchor_prefilter() {
+        use crate::{Pattern, PatternId, PatternSet};
+        use std::str::FromStr;
+
+        // Each pattern's rare-byte anchor sits past offset 0, so `select` must
+        // still find both matches even though it rules out most offsets via the
+        // anchor check before ever calling `pattern_matches`.
+        let bytes = [0x00, 0x10, 0xc9, 0x20, 0x00, 0x30, 0xfa, 0x40];
+        let set = PatternSet::new(vec![
+            Pattern::from_str("10 c9 20").unwrap(),
+            Pattern::from_str("30 fa 40").unwrap(),
+        ]);
+
+        assert_eq!(
+            crate::scan_multiple(Cursor::new(bytes), &set).unwrap(),
+            vec![(PatternId(0), 1), (PatternId(1), 5)]
+        );
+    }
+
+    #[test]
+    fn scan_fixed_high_wildcard_low_nibble() {
+        // "4?": high nibble fixed to 4, low nibble wildcarded.
+        let bytes = [0x10, 0x4a, 0x8b, 0x20];
+        let pattern = "4? 8b";
+
+        assert_eq!(crate::scan(Cursor::new(bytes), pattern).unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn scan_low_nibble_wildcard() {
+        // "4?": high nibble fixed to 4, low nibble wildcarded.
+        let bytes = [0x10, 0x4a, 0x4b, 0x20];
+        let pattern = "4?";
+
+        assert_eq!(
+            crate::scan(Cursor::new(bytes), pattern).unwrap(),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn scan_wildcard_high_fixed_low_nibble() {
+        // "?a": high nibble wildcarded, low nibble fixed to a.
+        let bytes = [0x10, 0x1a, 0xfa, 0x20];
+        let pattern = "?a";
+
+        assert_eq!(
+            crate::scan(Cursor::new(bytes), pattern).unwrap(),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn scan_masked_no_match() {
+        let bytes = [0x10, 0x5a, 0x20];
+        let pattern = "4?";
+
+        assert_eq!(crate::scan(Cursor::new(bytes), pattern).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn scan_rejects_invalid_nibble() {
+        let bytes = [0x10, 0x20];
+        let pattern = "g?";
+
+        assert!(crate::scan(Cursor::new(bytes), pattern).is_err());
+    }
+
+    #[test]
+    fn scan_last_match_simple() {
+        let bytes = [0x10, 0x20, 0x30, 0x10, 0x20, 0x30];
+        let pattern = "10 20 30";
+
+        assert_eq!(
+            crate::scan_last_match(Cursor::new(bytes), pattern)
+                .unwrap()
+                .unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn forward_and_backward_agree_on_trailing_bytes() {
+        // A pattern running off the end of the data must not match against the
+        // buffer's zero padding, so forward `scan` and backward `scan_last_match`
+        // report the same thing.
+        let bytes = [0x01, 0x02, 0x03];
+        let pattern = "03 00";
+
+        assert_eq!(crate::scan(Cursor::new(bytes), pattern).unwrap(), vec![]);
+        assert!(crate::scan_last_match(Cursor::new(bytes), pattern)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn forward_and_backward_agree_on_empty_pattern() {
+        // A zero-length pattern matches at every offset, including one past the
+        // end of the data; both directions must report the same count.
+        use crate::ReverseMatches;
+
+        let bytes = [0x01, 0x02, 0x03];
+        let pattern = "";
+
+        assert_eq!(
+            crate::scan(Cursor::new(bytes), pattern).unwrap(),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(
+            crate::scan_last_match(Cursor::new(bytes), pattern)
+                .unwrap()
+                .unwrap(),
+            3
+        );
+        let matches = ReverseMatches::from_pattern_str(Cursor::new(bytes), pattern).unwrap();
+        assert_eq!(matches.collect::<Vec<_>>(), vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn scan_last_match_no_match() {
+        let bytes = [0x10, 0x20, 0x30];
+        let pattern = "40 50";
+
+        assert!(crate::scan_last_match(Cursor::new(bytes), pattern)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn reverse_matches_highest_to_lowest() {
+        use crate::ReverseMatches;
+
+        let bytes = [0x10, 0x20, 0x30, 0x10, 0x20, 0x30];
+        let matches = ReverseMatches::from_pattern_str(Cursor::new(bytes), "10 20 30").unwrap();
+
+        assert_eq!(matches.collect::<Vec<_>>(), vec![3, 0]);
+    }
+
+    #[test]
+    fn scan_all_in_slice_multiple_matches() {
+        use crate::{scan_all_in_slice, Pattern};
+        use std::str::FromStr;
+
+        let bytes = [0x10, 0x20, 0x30, 0x10, 0x20, 0x30];
+        let pattern = Pattern::from_str("10 20 30").unwrap();
+
+        assert_eq!(scan_all_in_slice(&bytes, &pattern), vec![0, 3]);
+    }
+
+    #[test]
+    fn scan_all_in_slice_no_match() {
+        use crate::{scan_all_in_slice, Pattern};
+        use std::str::FromStr;
+
+        let bytes = [0x10, 0x20, 0x30];
+        let pattern = Pattern::from_str("40 50").unwrap();
+
+        assert_eq!(scan_all_in_slice(&bytes, &pattern), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn scan_all_in_slice_pattern_longer_than_slice() {
+        use crate::{scan_all_in_slice, Pattern};
+        use std::str::FromStr;
+
+        let bytes = [0x10, 0x20];
+        let pattern = Pattern::from_str("10 20 30").unwrap();
+
+        assert_eq!(scan_all_in_slice(&bytes, &pattern), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn scan_all_in_slice_empty_pattern() {
+        use crate::{scan_all_in_slice, Pattern};
+        use std::str::FromStr;
+
+        let bytes = [0x10, 0x20, 0x30];
+        let pattern = Pattern::from_str("").unwrap();
+
+        assert_eq!(scan_all_in_slice(&bytes, &pattern), vec![0, 1, 2, 3]);
+    }
+
     #[test]
     fn find_across_chunk_boundary() {
         let mut bytes = vec![0; super::CHUNK_SIZE - 2];
@@ -454,8 +1399,38 @@ mod tests {
         bytes.push(0xdd);
         let pattern = "aa bb cc dd";
 
-        assert!(crate::scan_first_match(Cursor::new(bytes), &pattern)
+        assert!(crate::scan_first_match(Cursor::new(bytes), pattern)
             .unwrap()
             .is_some())
     }
 }
+
+// Exercises the crate's own `Read` trait directly (rather than the blanket
+// `std::io::Read` impl above), so the `no_std` configuration is actually
+// verified rather than only compiled.
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_tests {
+    use crate::{scan, Error, Read};
+    use alloc::vec;
+
+    struct SliceReader<'a> {
+        data: &'a [u8],
+    }
+
+    impl<'a> Read for SliceReader<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+            let n = buf.len().min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn scan_over_custom_read_without_std() {
+        let bytes = [0x10, 0x20, 0x30, 0x40];
+        let reader = SliceReader { data: &bytes };
+
+        assert_eq!(scan(reader, "20 30").unwrap(), vec![1]);
+    }
+}
@@ -4,7 +4,7 @@ use std::fmt::{self, Display};
 fn main() {
     let bytes = [0x10, 0x20, 0x30, 0x40, 0x10, 0x20, 0x50, 0x50, 0x90, 0x90, 0x80];
     let pattern = "90 ? 80";
-    let matches = find_pattern(&bytes, &pattern);
+    let matches = find_pattern(&bytes, pattern);
 
     println!("matches -> {:?}", matches);
 }